@@ -0,0 +1,143 @@
+use crate::core::{
+    hypergraph::Hypergraph,
+    model::{Model, Size},
+    simulation::Simulation
+};
+
+use std::error::Error;
+use std::fs;
+
+use clap::Clap;
+use serde_json;
+
+/// Estimates the generating parameters of an observed hypergraph
+#[derive(Clap, Debug)]
+pub struct Fit {
+    /// Path to a file to read the observed simulation from
+    pub path: String,
+
+    /// Whether to report the Bayesian posterior instead of the point estimate
+    #[clap(long)]
+    pub bayesian: bool,
+
+    /// Concentration of the symmetric Dirichlet prior on `(pv, pe, pd)`
+    #[clap(long, default_value = "1.0")]
+    pub prior: f64,
+}
+
+impl Fit {
+    /// Executes the `fit` subcommand.
+    pub fn execute(self: &Self) {
+        let file = fs::read_to_string(self.path.as_str()).unwrap();
+        let simulation: Simulation = serde_json::from_str(file.as_str()).unwrap();
+
+        self.fit(&simulation).unwrap();
+    }
+
+    /// Estimates the model from the observed hypergraph and reports the goodness of fit.
+    fn fit(self: &Self, simulation: &Simulation) -> Result<(), Box<dyn Error>> {
+        let hypergraph = &simulation.hypergraph;
+
+        // Recover the event counts from the recorded growth of the hypergraph. Every vertex-arrival
+        // event adds one vertex and one edge, every edge-arrival event adds one edge, and every
+        // deactivation event adds neither, so the three counts are fully determined by the totals.
+        let arrivals_v = (hypergraph.vertices - 1) as u64;
+        let arrivals_e = (hypergraph.edges.len() as u64 - 1).checked_sub(arrivals_v)
+            .ok_or("malformed simulation: fewer edges than vertex arrivals")?;
+        let deactivations = simulation.steps.checked_sub(arrivals_v + arrivals_e)
+            .ok_or("malformed simulation: arrivals exceed recorded steps")?;
+
+        let counts = [arrivals_v as f64, arrivals_e as f64, deactivations as f64];
+
+        // Estimate `(pv, pe, pd)` either by maximum likelihood or as the Dirichlet posterior mean.
+        let [pv, pe, pd] = if self.bayesian {
+            self.posterior_mean(&counts)
+        }
+        else {
+            let total: f64 = counts.iter().sum();
+
+            [counts[0] / total, counts[1] / total, counts[2] / total]
+        };
+
+        // Estimate the hyperedge-size distribution from the empirical edge-size histogram, skipping
+        // the initial edge of the seed hypergraph.
+        let m = Self::size_histogram(hypergraph);
+        let model = Model::new(pv, pe, pd, m)?;
+
+        println!("Fitted model:");
+        println!("  pv = {:.4}", pv);
+        println!("  pe = {:.4}", pe);
+        println!("  pd = {:.4}", pd);
+        println!("  E[m] = {:.4}", model.m.mean());
+
+        if self.bayesian {
+            for (name, &n) in ["pv", "pe", "pd"].iter().zip(counts.iter()) {
+                let (lo, hi) = self.credible_interval(n, &counts);
+
+                println!("  95% credible interval for {}: [{:.4}, {:.4}]", name, lo, hi);
+            }
+        }
+
+        println!("Goodness of fit (sum of squared error): {:.6}", Self::goodness_of_fit(&model, simulation));
+
+        Ok(())
+    }
+
+    /// The posterior mean of `(pv, pe, pd)` under a symmetric Dirichlet prior.
+    fn posterior_mean(self: &Self, counts: &[f64; 3]) -> [f64; 3] {
+        let total: f64 = counts.iter().map(|&n| n + self.prior).sum();
+
+        [
+            (counts[0] + self.prior) / total,
+            (counts[1] + self.prior) / total,
+            (counts[2] + self.prior) / total,
+        ]
+    }
+
+    /// A 95% credible interval for one component, derived from its `Beta` marginal of the Dirichlet
+    /// posterior via a normal approximation around the closed-form mean and variance.
+    fn credible_interval(self: &Self, n: f64, counts: &[f64; 3]) -> (f64, f64) {
+        let total: f64 = counts.iter().map(|&n| n + self.prior).sum();
+        let a = n + self.prior;
+
+        let mean = a / total;
+        let var = a * (total - a) / (total * total * (total + 1.0));
+        let sd = var.sqrt();
+
+        (mean - 1.96 * sd, mean + 1.96 * sd)
+    }
+
+    /// Builds the empirical hyperedge-size distribution from the observed edges.
+    fn size_histogram(hypergraph: &Hypergraph) -> Size {
+        let mut histogram: Vec<(usize, f64)> = Vec::new();
+
+        for edge in hypergraph.edges.iter().skip(1) {
+            let size = edge.len();
+
+            match histogram.iter_mut().find(|(s, _)| *s == size) {
+                Some((_, count)) => *count += 1.0,
+                None             => histogram.push((size, 1.0)),
+            }
+        }
+
+        Size::Empirical(histogram.into())
+    }
+
+    /// Compares the fitted theoretical degree distribution with the empirical one.
+    ///
+    /// `degree_distribution` returns an unnormalized density, so it is renormalized into a pmf over
+    /// the observed support `1..=max_degree` before the comparison; otherwise the squared error is
+    /// dominated by the scale offset at `k = 1` rather than the shape of the fit.
+    fn goodness_of_fit(model: &Model, simulation: &Simulation) -> f64 {
+        let theory = model.degree_distribution();
+        let empirical = simulation.hypergraph.degree_distribution();
+
+        let max_degree = empirical.keys().copied().max().unwrap_or(0);
+        let mass: f64 = (1..=max_degree).map(|k| theory(k as f64)).sum();
+
+        empirical
+            .iter()
+            .map(|(&k, &p)| (theory(k as f64) / mass - p as f64).powi(2))
+            .sum()
+    }
+}
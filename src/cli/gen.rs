@@ -1,13 +1,13 @@
 use crate::core::{
     hypergraph::Hypergraph,
-    model::Model,
+    model::{Model, Size},
     simulation::Simulation
 };
 
-use std::{fs::File, io::Write, time::Instant};
+use std::{error::Error, fs::File, io::Write, time::Instant};
 
 use clap::Clap;
-use rand::SeedableRng;
+use rand::{Rng, SeedableRng};
 use rand_pcg::Pcg64Mcg;
 use rayon::prelude::*;
 use serde_json;
@@ -25,9 +25,10 @@ pub struct Gen {
     #[clap(default_value = "0.21")]
     pub pd: f64,
 
-    /// Size of hyperedges
+    /// Distribution of hyperedge sizes: an integer, `poisson:λ`, `pareto:scale,shape`,
+    /// or `empirical:1=0.5,2=0.3,3=0.2`
     #[clap(default_value = "3")]
-    pub m: usize,
+    pub m: String,
 
     /// Number of iterations to perform
     #[clap(default_value = "1000")]
@@ -41,10 +42,19 @@ pub struct Gen {
     #[clap(long)]
     pub par: bool,
 
+    /// Whether hyperedges must consist of distinct vertices (simple hyperedges)
+    #[clap(long)]
+    pub distinct: bool,
+
     /// Number of retries to perform until the model finishes with success
     #[clap(long, default_value = "100")]
     pub retries: u32,
 
+    /// Seed for reproducible generation; run `i` is seeded with `seed ^ i`.
+    /// When omitted, a fresh seed is drawn from the system entropy
+    #[clap(long)]
+    pub seed: Option<u64>,
+
     /// Template path to a JSON file to save the generated hypergraph to
     #[clap(long, default_value = "data/hypergraph")]
     pub save: String,
@@ -69,10 +79,19 @@ impl Gen {
     }
 
     /// Generates a hypergraph.
-    fn generate(self: &Self, i: u32) -> Result<(), &'static str> {
-        let model = Model::new(self.pv, self.pe, self.pd, self.m)?;
+    fn generate(self: &Self, i: u32) -> Result<(), Box<dyn Error>> {
+        let m = self.m.parse::<Size>()?;
+        let model = Model::new(self.pv, self.pe, self.pd, m)?;
         let instant = Instant::now();
-        let simulation = model.generate(self.t, self.retries)?;
+
+        // Seed run `i` deterministically when a seed is given, otherwise draw one from entropy so
+        // that the effective seed is still recorded and the run can be replayed.
+        let seed = match self.seed {
+            Some(seed) => seed ^ i as u64,
+            None       => Pcg64Mcg::from_entropy().gen(),
+        };
+
+        let simulation = model.generate(self.t, self.retries, seed, self.distinct)?;
 
         println!("[{}]: {:?} elapsed", i, instant.elapsed());
 
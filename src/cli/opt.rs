@@ -1,4 +1,4 @@
-use crate::cli::{gen::Gen, plot::Plot};
+use crate::cli::{fit::Fit, gen::Gen, plot::Plot};
 
 use clap::Clap;
 
@@ -15,4 +15,5 @@ pub struct Opt {
 pub enum Subcommand {
     Gen(Gen),   // Generates a hypergraph.
     Plot(Plot), // Plots a hypergraph.
+    Fit(Fit),   // Fits a model to a hypergraph.
 }
\ No newline at end of file
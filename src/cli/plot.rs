@@ -21,6 +21,10 @@ pub struct Plot {
     /// Path to a file to save the plot to
     #[clap(long)]
     pub save: String,
+
+    /// Which chart to draw: `degree` for the degree distribution, `theta` for `θ` against time
+    #[clap(long, default_value = "degree")]
+    pub kind: String,
 }
 
 impl Plot {
@@ -29,11 +33,15 @@ impl Plot {
         let file = fs::read_to_string(self.path.as_str()).unwrap();
         let simulation: Simulation = serde_json::from_str(file.as_str()).unwrap();
 
-        self.plot(&simulation);
+        match self.kind.as_str() {
+            "degree" => self.plot_degree(&simulation),
+            "theta"  => self.plot_theta(&simulation),
+            other    => panic!("Unknown plot kind `{}`", other),
+        }.unwrap();
     }
 
-    /// Plots the degree distribution of the generated hypergraph.
-    fn plot(self: &Self, simulation: &Simulation) -> Result<(), Box<dyn std::error::Error>> {
+    /// Plots the empirical degree distribution together with the theoretical one.
+    fn plot_degree(self: &Self, simulation: &Simulation) -> Result<(), Box<dyn std::error::Error>> {
         // Compute the empirical degree distribution to display.
         let distribution = simulation.hypergraph.degree_distribution();
 
@@ -80,6 +88,72 @@ impl Plot {
             .label("Empirical distribution")
             .legend(|(x, y)| Circle::new((x, y), 3, BLACK));
 
+        // Overlay the theoretical degree distribution as a line over the same log-log axis.
+        let theory = simulation.model.degree_distribution();
+        let max_degree = *distribution.keys().max().unwrap();
+
+        chart
+            .draw_series(LineSeries::new(
+                (1..=max_degree).map(|k| (k as f32, theory(k as f64) as f32)),
+                &RED,
+            ))?
+            .label("Theoretical distribution")
+            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &RED));
+
+        // Configure the legend.
+        chart
+            .configure_series_labels()
+            .background_style(&WHITE.mix(0.8))
+            .border_style(&BLACK)
+            .draw()?;
+
+        // Show the result.
+        root.present()?;
+
+        Ok(())
+    }
+
+    /// Plots the expected deactivated-degree `θ` against the step index.
+    fn plot_theta(self: &Self, simulation: &Simulation) -> Result<(), Box<dyn std::error::Error>> {
+        let theta = &simulation.theta;
+
+        let x = 0f32..theta.len() as f32;
+        let y = theta.iter().cloned().fold(f64::INFINITY, f64::min) as f32..
+                theta.iter().cloned().fold(f64::NEG_INFINITY, f64::max) as f32;
+
+        // Construct the plot.
+        let root = BitMapBackend::new(self.save.as_str(), (640, 480))
+            .into_drawing_area();
+
+        root.fill(&WHITE)?;
+
+        let mut chart = ChartBuilder::on(&root)
+            .x_label_area_size(35)
+            .y_label_area_size(40)
+            .right_y_label_area_size(40)
+            .margin(5)
+            .caption("Expected deactivated degree", ("sans-serif", 18.0).into_font())
+            .build_cartesian_2d(x.clone(), y.clone())?
+            .set_secondary_coord(x, y);
+
+        // Configure axes.
+        chart
+            .configure_mesh()
+            .disable_x_mesh()
+            .disable_y_mesh()
+            .x_desc("Step `t`")
+            .y_desc("θ")
+            .draw()?;
+
+        // Plot `θ` against time on the secondary coordinate system.
+        chart
+            .draw_secondary_series(LineSeries::new(
+                theta.iter().enumerate().map(|(t, &o)| (t as f32, o as f32)),
+                &BLUE,
+            ))?
+            .label("θ(t)")
+            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &BLUE));
+
         // Configure the legend.
         chart
             .configure_series_labels()
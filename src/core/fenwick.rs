@@ -93,4 +93,43 @@ impl Fenwick {
     {
         (0..m).map(|_| self.sample_one(random)).collect()
     }
+
+    /// A weighted sampling of `m` distinct elements without replacement.
+    ///
+    /// Each chosen element has its weight temporarily set to 0 so that it cannot be drawn again;
+    /// once all `m` elements are sampled the original weights are restored, leaving the tree
+    /// unchanged. Since every draw and every restoration costs `O(log^2 n)`, the complexity of the
+    /// algorithm is `O(m log^2 n)`. Returns an error if fewer than `m` elements carry weight.
+    pub fn sample_many_distinct<R>(self: &mut Self, m: usize, random: &mut R)
+        -> Result<Vec<u32>, &'static str>
+        where R: Rng + ?Sized
+    {
+        let mut chosen = Vec::with_capacity(m);
+        let mut removed = Vec::with_capacity(m);
+
+        for _ in 0..m {
+            if self.total <= 0 {
+                break;
+            }
+
+            let v = self.sample_one(random);
+            let w = self.get(v as usize);
+
+            self.set(v as usize, 0);
+
+            chosen.push(v);
+            removed.push((v, w));
+        }
+
+        // Restore the original weights regardless of whether enough elements were found.
+        for &(v, w) in &removed {
+            self.set(v as usize, w);
+        }
+
+        if chosen.len() < m {
+            return Err("Fewer than `m` active vertices remain");
+        }
+
+        Ok(chosen)
+    }
 }
@@ -5,11 +5,214 @@ use crate::core::{
 };
 
 use std::error::Error;
+use std::str::FromStr;
 
 use rand::{Rng, SeedableRng};
 use rand_pcg::Pcg64Mcg;
 use serde::{Serialize, Deserialize};
 
+/// The distribution `Y` of cardinalities of hyperedges.
+///
+/// A fresh size `Y_t >= 1` is drawn from this distribution at every vertex- or edge-arrival event,
+/// which lets the model reproduce datasets whose hyperedges vary in size rather than being forced
+/// to a single cardinality.
+#[derive(Clone, Serialize, Deserialize)]
+pub enum Size {
+    /// A fixed cardinality `m`.
+    Constant(usize),
+
+    /// A Poisson distribution with rate `λ`, shifted so that sizes are at least 1.
+    Poisson(f64),
+
+    /// A Pareto distribution with the given `scale` and `shape`, rounded to an integer.
+    Pareto(f64, f64),
+
+    /// An arbitrary empirical distribution over `(size, weight)` pairs.
+    Empirical(Alias),
+}
+
+impl Size {
+    /// Draws a fresh cardinality `Y_t`.
+    pub fn sample<R>(self: &Self, random: &mut R) -> usize
+        where R: Rng + ?Sized
+    {
+        match self {
+            Size::Constant(m)          => *m,
+            Size::Poisson(lambda)      => 1 + poisson(*lambda, random) as usize,
+            Size::Pareto(scale, shape) => (pareto(*scale, *shape, random).round() as usize).max(1),
+            Size::Empirical(alias)     => alias.sample(random).max(1),
+        }
+    }
+
+    /// The mean cardinality, used by the analytic degree distribution.
+    ///
+    /// For `Pareto` this is the mean of the continuous distribution; it does not account for the
+    /// rounding and the clamping to at least 1 that `sample` applies.
+    pub fn mean(self: &Self) -> f64 {
+        match self {
+            Size::Constant(m)          => *m as f64,
+            Size::Poisson(lambda)      => 1.0 + lambda,
+            Size::Pareto(scale, shape) => scale * shape / (shape - 1.0),
+            Size::Empirical(alias)     => alias.mean(),
+        }
+    }
+
+    /// Ensures that the distribution is well-formed.
+    fn validate(self: &Self) -> Result<(), Box<dyn Error>> {
+        match self {
+            Size::Constant(m) if *m < 1 =>
+                Err("Expected `m` to be a positive integer".into()),
+            Size::Poisson(lambda) if *lambda <= 0.0 =>
+                Err("Expected the Poisson rate to be positive".into()),
+            Size::Pareto(scale, shape) if *scale <= 0.0 || *shape <= 1.0 =>
+                Err("Expected the Pareto `scale > 0` and `shape > 1`".into()),
+            Size::Empirical(alias) if alias.is_empty() =>
+                Err("Expected a non-empty empirical distribution".into()),
+            Size::Empirical(alias) if alias.values.iter().any(|&k| k < 1) =>
+                Err("Expected empirical sizes to be positive integers".into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Parses a `Size` from a CLI string.
+///
+/// Accepts a bare integer (`3`) for a constant size, or a tagged form for the other distributions:
+/// `poisson:4.0`, `pareto:1.0,2.5`, and `empirical:1=0.5,2=0.3,3=0.2`.
+impl FromStr for Size {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            None => s.parse::<usize>()
+                .map(Size::Constant)
+                .map_err(|_| format!("Invalid size `{}`", s)),
+
+            Some(("poisson", rest)) => rest.parse::<f64>()
+                .map(Size::Poisson)
+                .map_err(|_| format!("Invalid Poisson rate `{}`", rest)),
+
+            Some(("pareto", rest)) => {
+                let (scale, shape) = rest.split_once(',')
+                    .ok_or_else(|| format!("Expected `pareto:scale,shape`, got `{}`", rest))?;
+
+                Ok(Size::Pareto(
+                    scale.parse().map_err(|_| format!("Invalid scale `{}`", scale))?,
+                    shape.parse().map_err(|_| format!("Invalid shape `{}`", shape))?,
+                ))
+            }
+
+            Some(("empirical", rest)) => {
+                let pmf = rest.split(',')
+                    .map(|pair| {
+                        let (size, weight) = pair.split_once('=')
+                            .ok_or_else(|| format!("Expected `size=weight`, got `{}`", pair))?;
+
+                        Ok((
+                            size.parse().map_err(|_| format!("Invalid size `{}`", size))?,
+                            weight.parse().map_err(|_| format!("Invalid weight `{}`", weight))?,
+                        ))
+                    })
+                    .collect::<Result<Vec<(usize, f64)>, String>>()?;
+
+                Ok(Size::Empirical(Alias::from(pmf)))
+            }
+
+            Some((tag, _)) => Err(format!("Unknown size distribution `{}`", tag)),
+        }
+    }
+}
+
+/// An alias table (Vose's method) for `O(1)` sampling from a discrete distribution.
+///
+/// It serializes as the original `(size, weight)` pmf and rebuilds its columns on deserialization,
+/// so a saved model records only the distribution and not the derived table.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(from = "Vec<(usize, f64)>", into = "Vec<(usize, f64)>")]
+pub struct Alias {
+    /// The support, that is, the sizes that can be drawn.
+    values: Vec<usize>,
+
+    /// The probability of keeping a column rather than following its alias.
+    prob: Vec<f64>,
+
+    /// The alias column for each column.
+    alias: Vec<usize>,
+
+    /// The original pmf, retained for (re)serialization.
+    pmf: Vec<(usize, f64)>,
+}
+
+impl Alias {
+    /// Whether the distribution has no support.
+    fn is_empty(self: &Self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// The mean of the distribution.
+    fn mean(self: &Self) -> f64 {
+        let total: f64 = self.pmf.iter().map(|&(_, w)| w).sum();
+
+        self.pmf.iter().map(|&(k, w)| k as f64 * w).sum::<f64>() / total
+    }
+
+    /// Draws a size in `O(1)` by flipping a biased coin on a uniformly chosen column.
+    fn sample<R>(self: &Self, random: &mut R) -> usize
+        where R: Rng + ?Sized
+    {
+        let i = random.gen_range(0..self.values.len());
+
+        if random.gen::<f64>() < self.prob[i] {
+            self.values[i]
+        }
+        else {
+            self.values[self.alias[i]]
+        }
+    }
+}
+
+impl From<Vec<(usize, f64)>> for Alias {
+    fn from(pmf: Vec<(usize, f64)>) -> Self {
+        let n = pmf.len();
+        let total: f64 = pmf.iter().map(|&(_, w)| w).sum();
+
+        let values = pmf.iter().map(|&(k, _)| k).collect();
+        let mut prob = vec![0.0; n];
+        let mut alias = vec![0; n];
+
+        // Scale the probabilities so that the average column has mass 1.
+        let mut scaled: Vec<f64> = pmf.iter().map(|&(_, w)| w / total * n as f64).collect();
+
+        let mut small: Vec<usize> = (0..n).filter(|&i| scaled[i] < 1.0).collect();
+        let mut large: Vec<usize> = (0..n).filter(|&i| scaled[i] >= 1.0).collect();
+
+        while let (Some(&l), Some(&g)) = (small.last(), large.last()) {
+            small.pop();
+            prob[l] = scaled[l];
+            alias[l] = g;
+
+            scaled[g] -= 1.0 - scaled[l];
+
+            if scaled[g] < 1.0 {
+                large.pop();
+                small.push(g);
+            }
+        }
+
+        for i in small.into_iter().chain(large) {
+            prob[i] = 1.0;
+        }
+
+        Self { values, prob, alias, pmf }
+    }
+}
+
+impl From<Alias> for Vec<(usize, f64)> {
+    fn from(alias: Alias) -> Self {
+        alias.pmf
+    }
+}
+
 /// The random preferential attachment hypergraph model with vertex deactivation,
 /// described by a 5-tuple `H(H_0, p_v, p_e, p_d, Y)`.
 ///
@@ -25,7 +228,7 @@ use serde::{Serialize, Deserialize};
 ///
 /// The described model generates hypergraphs whose degree distribution follows a power-law
 /// distribution with an exponential cutoff.
-#[derive(Copy, Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Model {
     /// Probability of the vertex arrival event.
     pub pv: f64,
@@ -36,14 +239,14 @@ pub struct Model {
     /// Probability of the vertex deactivation event.
     pub pd: f64,
 
-    /// Sizes of hyperedges.
-    pub m: usize,
+    /// Distribution of cardinalities of hyperedges.
+    pub m: Size,
 }
 
 impl Model {
     /// Creates a model with the specified parameters.
     /// Ensures that the provided parameters are correct.
-    pub fn new(pv: f64, pe: f64, pd: f64, m: usize) -> Result<Model, Box<dyn Error>> {
+    pub fn new(pv: f64, pe: f64, pd: f64, m: Size) -> Result<Model, Box<dyn Error>> {
         if pv < 0.0 || pe < 0.0 || pd < 0.0 {
             return Err("Expected `pv`, `pe` and `pd` to be positive".into());
         }
@@ -53,16 +256,16 @@ impl Model {
         if pv <= pd {
             return Err("Expected `pv > pd` to hold".into());
         }
-        if m < 1 {
-            return Err("Expected `m` to be a positive integer".into());
-        }
+        m.validate()?;
 
         Ok(Model { pv, pd, pe, m })
     }
 
-    /// The size of a hyperedge at step `t`.
-    pub fn size(self: &Self, t: u64) -> usize {
-        self.m
+    /// Draws the size of a hyperedge at step `t`.
+    pub fn size<R>(self: &Self, _t: u64, random: &mut R) -> usize
+        where R: Rng + ?Sized
+    {
+        self.m.sample(random)
     }
 
     /// Generates a hypergraph according to the model.
@@ -72,17 +275,21 @@ impl Model {
     /// * `retries` - the number of times to retry a simulation;
     ///               simulation failures are expected to happen, for example,
     ///               if we randomly deactivate all active vertices.
+    /// * `seed` - the seed that makes the generation reproducible; it is recorded in the
+    ///            resulting `Simulation` so a saved hypergraph knows how to regenerate itself.
+    /// * `distinct` - whether hyperedges must consist of distinct vertices.
     ///
     /// # Returns
     /// A `Result` instance that contains either a simulation result or an error message.
-    pub fn generate(self: &Self, steps: u64, retries: u32) -> Result<Simulation, Box<dyn Error>> {
-        let mut random = Pcg64Mcg::from_entropy();
-        let mut result = Simulation::run(self, steps, &mut random);
+    pub fn generate(self: &Self, steps: u64, retries: u32, seed: u64, distinct: bool) -> Result<Simulation, Box<dyn Error>> {
+        let mut random = Pcg64Mcg::seed_from_u64(seed);
+        let mut result = Simulation::run(self, steps, seed, distinct, &mut random);
 
-        // Retry in case if a simulation fails.
-        for _ in 0..retries {
+        // Retry in case if a simulation fails, deriving a fresh deterministic substream each time.
+        for r in 1..=retries {
             if result.is_err() {
-                result = Simulation::run(self, steps, &mut random);
+                let mut random = Pcg64Mcg::seed_from_u64(seed ^ r as u64);
+                result = Simulation::run(self, steps, seed, distinct, &mut random);
             }
             else {
                 break;
@@ -97,27 +304,218 @@ impl Model {
         let pv = self.pv;
         let pe = self.pe;
         let pd = self.pd;
-        let m = self.m as f64;
+        let m = self.m.mean();
 
         let g = (pv * (m - 1.) + pe * m) / (pv * (m - 1.) + pe * m + pd);
-        let o = self.theta(0., 100000);
+        let o = self.theta(0., 100, 1e-10);
         let d = pd / ((pv + pe) * m - pd * o);
         let b = (m * (pv + pe) - pd * o) / (pv * (m - 1.) + pe * m + pd);
         let c = pv / g * gamma(1. + 1./b) / b;
 
-        // The gamma function.
-        fn gamma(x: f64) -> f64 {
-            panic!("Not implemented.");
+        Box::new(move |x| c/pv * g.powf(x)/x.powf(1.0/ b) * (1.0/x + d))
+    }
+
+    /// Computes `theta`, the expected degree of a deactivated vertex, as the fixed point of the
+    /// model's self-consistency map `F`.
+    ///
+    /// The iteration is accelerated with Aitken's delta-squared method: it starts from `seed`,
+    /// performs at most `n` acceleration steps, and stops early once two successive estimates
+    /// differ by less than `tol`.
+    fn theta(self: &Self, seed: f64, n: u32, tol: f64) -> f64 {
+        aitken(seed, n, tol, |o| self.theta_map(o))
+    }
+
+    /// The self-consistency map `F` for `theta`.
+    ///
+    /// For a trial value `o`, it builds the theoretical (unnormalized) degree distribution and
+    /// returns its size-biased mean `Σ k² q(k) / Σ k q(k)`, that is, the expected degree of a
+    /// preferentially selected vertex. Its fixed point is the `theta` we are after.
+    fn theta_map(self: &Self, o: f64) -> f64 {
+        let pv = self.pv;
+        let pe = self.pe;
+        let pd = self.pd;
+        let m = self.m.mean();
+
+        let g = (pv * (m - 1.) + pe * m) / (pv * (m - 1.) + pe * m + pd);
+        let b = (m * (pv + pe) - pd * o) / (pv * (m - 1.) + pe * m + pd);
+        let d = pd / ((pv + pe) * m - pd * o);
+
+        // `g < 1`, so the series converges; a few thousand terms are well past machine precision.
+        let mut num = 0.0;
+        let mut den = 0.0;
+
+        for k in 1..=10000 {
+            let x = k as f64;
+            let q = g.powf(x) / x.powf(1.0 / b) * (1.0 / x + d);
+
+            num += x * x * q;
+            den += x * q;
         }
 
-        Box::new(move |x| c/pv * g.powf(x)/x.powf(1.0/ b) * (1.0/x + d))
+        num / den
+    }
+}
+
+/// Draws a Poisson-distributed value with rate `lambda` using Knuth's multiplicative method.
+fn poisson<R>(lambda: f64, random: &mut R) -> u64
+    where R: Rng + ?Sized
+{
+    let l = (-lambda).exp();
+
+    let mut k = 0;
+    let mut p = 1.0;
+
+    loop {
+        k += 1;
+        p *= random.gen::<f64>();
+
+        if p <= l {
+            break;
+        }
+    }
+
+    k - 1
+}
+
+/// Draws a Pareto-distributed value with the given `scale` and `shape` by inverting its CDF.
+fn pareto<R>(scale: f64, shape: f64, random: &mut R) -> f64
+    where R: Rng + ?Sized
+{
+    let u: f64 = random.gen();
+
+    scale / (1.0 - u).powf(1.0 / shape)
+}
+
+/// Finds a fixed point of `f` using Aitken's delta-squared acceleration.
+///
+/// Starting from `x0`, each step forms the plain iterates `x1 = f(x0)` and `x2 = f(x1)` and the
+/// accelerated estimate `x̂ = x0 − (x1 − x0)² / (x2 − 2·x1 + x0)`, then restarts from `x̂`. At most
+/// `n` steps are taken; the loop returns early once `|x̂ − x0| < tol`. When the denominator is
+/// near zero the plain iterate `x2` is used instead.
+fn aitken<F>(mut x0: f64, n: u32, tol: f64, f: F) -> f64
+    where F: Fn(f64) -> f64
+{
+    const EPS: f64 = 1e-12;
+
+    for _ in 0..n {
+        let x1 = f(x0);
+        let x2 = f(x1);
+        let denom = x2 - 2.0 * x1 + x0;
+
+        let x_hat = if denom.abs() < EPS {
+            x2
+        }
+        else {
+            x0 - (x1 - x0).powi(2) / denom
+        };
+
+        if (x_hat - x0).abs() < tol {
+            return x_hat;
+        }
+
+        x0 = x_hat;
+    }
+
+    x0
+}
+
+/// Evaluates the gamma function `Γ(x)` using the Lanczos approximation.
+///
+/// For `x < 0.5` the reflection formula `Γ(x) = π / (sin(πx) · Γ(1 − x))` is used to move the
+/// argument into the region where the approximation is accurate; otherwise the standard `g = 7`,
+/// `n = 8` coefficient table is summed directly.
+fn gamma(x: f64) -> f64 {
+    // Lanczos coefficients for `g = 7`, `n = 8`.
+    const G: f64 = 7.0;
+    const C: [f64; 9] = [
+        0.999_999_999_999_809_93,
+        676.520_368_121_885_1,
+        -1259.139_216_722_402_8,
+        771.323_428_777_653_13,
+        -176.615_029_162_140_59,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+
+    if x < 0.5 {
+        std::f64::consts::PI / ((std::f64::consts::PI * x).sin() * gamma(1.0 - x))
+    }
+    else {
+        let z = x - 1.0;
+        let a = C[0] + (1..=8).map(|i| C[i] / (z + i as f64)).sum::<f64>();
+        let t = z + G + 0.5;
+
+        (2.0 * std::f64::consts::PI).sqrt() * t.powf(z + 0.5) * (-t).exp() * a
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{aitken, gamma, Size};
+
+    use rand::SeedableRng;
+    use rand_pcg::Pcg64Mcg;
+
+    #[test]
+    fn gamma_of_integers_is_factorial() {
+        // `Γ(n) = (n − 1)!` for positive integers.
+        let factorials = [1.0, 1.0, 2.0, 6.0, 24.0, 120.0];
+
+        for (n, &expected) in factorials.iter().enumerate() {
+            assert!((gamma(n as f64 + 1.0) - expected).abs() < 1e-6);
+        }
     }
 
-    /// Computes `theta` using the fixed-point iteration method.
-    fn theta(self: &Self, seed: f64, n: u32) -> f64 {
-        // May require the `rgsl` package and GNU Scientific Library.
-        // Unfortunately, it appears that there are no alternative libraries,
-        // which implement the hypergeometric function.
-        panic!("Not implemented.");
+    #[test]
+    fn gamma_of_one_half_is_sqrt_pi() {
+        assert!((gamma(0.5) - std::f64::consts::PI.sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn aitken_converges_on_a_contraction() {
+        // `x = cos(x)` is a contraction with the Dottie number as its fixed point.
+        let x = aitken(0.0, 100, 1e-12, |x: f64| x.cos());
+
+        assert!((x - x.cos()).abs() < 1e-10);
+        assert!((x - 0.739_085_133_215_160_6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn size_parses_every_tagged_form() {
+        assert!(matches!("3".parse::<Size>(), Ok(Size::Constant(3))));
+        assert!(matches!("poisson:4.0".parse::<Size>(), Ok(Size::Poisson(_))));
+        assert!(matches!("pareto:1.0,2.5".parse::<Size>(), Ok(Size::Pareto(_, _))));
+        assert!(matches!("empirical:1=0.5,2=0.5".parse::<Size>(), Ok(Size::Empirical(_))));
+
+        assert!("poisson:nope".parse::<Size>().is_err());
+        assert!("pareto:1.0".parse::<Size>().is_err());
+        assert!("weibull:1.0".parse::<Size>().is_err());
+    }
+
+    #[test]
+    fn empirical_samples_match_their_weights() {
+        // `2` is drawn three times as often as `1`; over many draws the frequencies should track
+        // the pmf, which exercises the alias table end to end.
+        let size = "empirical:1=0.25,2=0.75".parse::<Size>().unwrap();
+        let mut random = Pcg64Mcg::seed_from_u64(42);
+
+        let n = 100_000;
+        let twos = (0..n).filter(|_| size.sample(&mut random) == 2).count();
+
+        assert!(((twos as f64 / n as f64) - 0.75).abs() < 0.01);
+    }
+
+    #[test]
+    fn pareto_never_draws_below_one() {
+        let mut random = Pcg64Mcg::seed_from_u64(7);
+
+        // A small Pareto scale rounds toward 0 but must be clamped to at least 1.
+        let pareto = "pareto:0.3,2.0".parse::<Size>().unwrap();
+
+        for _ in 0..10_000 {
+            assert!(pareto.sample(&mut random) >= 1);
+        }
     }
 }
\ No newline at end of file
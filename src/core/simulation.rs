@@ -18,6 +18,9 @@ pub struct Simulation {
     /// The number of steps performed.
     pub steps: u64,
 
+    /// The seed that deterministically reproduces this simulation.
+    pub seed: u64,
+
     /// A vector of thetas, that is, the expected degrees of deactivated vertices.
     /// The size of this vector equals the number of steps.
     pub theta: Vec<f64>,
@@ -29,12 +32,15 @@ impl Simulation {
     /// # Arguments
     /// * `model` - the model according to which a hypergraph should be generated;
     /// * `steps` - the number of steps of the simulation to perform;
+    /// * `seed` - the seed that reproduces the simulation, recorded in the result;
+    /// * `distinct` - whether hyperedges must consist of distinct vertices (simple hyperedges)
+    ///                rather than multisets;
     /// * `random` - a random number generator.
     ///
     /// # Returns
     /// A `Simulation` object, which describes the result of the simulation.
     pub fn run<R>(
-        model: &Model, steps: u64, random: &mut R
+        model: &Model, steps: u64, seed: u64, distinct: bool, random: &mut R
     ) -> Result<Simulation, &'static str>
         where R: Rng + ?Sized
     {
@@ -67,8 +73,13 @@ impl Simulation {
             // Perform the vertex arrival event.
             if p <= model.pv {
                 let v = hypergraph.add_vertex();
-                let m = model.size(t);
-                let mut e = fenwick.sample_many(m - 1, random);
+                let m = model.size(t, random);
+                let mut e = if distinct {
+                    fenwick.sample_many_distinct(m - 1, random)?
+                }
+                else {
+                    fenwick.sample_many(m - 1, random)
+                };
 
                 e.push(v);
 
@@ -86,8 +97,13 @@ impl Simulation {
 
             // Perform the edge arrival event.
             else if p <= model.pv + model.pe {
-                let m = model.size(t);
-                let e = fenwick.sample_many(m, random);
+                let m = model.size(t, random);
+                let e = if distinct {
+                    fenwick.sample_many_distinct(m, random)?
+                }
+                else {
+                    fenwick.sample_many(m, random)
+                };
 
                 for &u in &e {
                     let deg = hypergraph.degree[u as usize];
@@ -115,6 +131,6 @@ impl Simulation {
             theta.push(active_squares as f64 / active_degrees as f64);
         }
 
-        Ok(Simulation { model: *model, hypergraph, steps, theta })
+        Ok(Simulation { model: model.clone(), hypergraph, steps, seed, theta })
     }
 }
\ No newline at end of file
@@ -5,6 +5,7 @@ use crate::cli::{
     opt::{Opt, Subcommand},
     gen::Gen,
     plot::Plot,
+    fit::Fit,
 };
 
 use clap::Clap;
@@ -15,5 +16,6 @@ fn main() {
     match opt.command {
         Subcommand::Gen(x)  => x.execute(),
         Subcommand::Plot(x) => x.execute(),
-    }.unwrap();
+        Subcommand::Fit(x)  => x.execute(),
+    }
 }
\ No newline at end of file